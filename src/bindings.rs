@@ -0,0 +1,130 @@
+use alloc::vec::Vec;
+
+use crate::input::{Input, InputState, KeyMods, KeyTypes};
+
+/// A source of input that a [`Binding`] can react to.
+#[derive(Clone, Debug)]
+pub enum Trigger<K: KeyTypes> {
+    /// A physical key.
+    PhysicalKey(K::KeyCode),
+    /// A logical key.
+    LogicalKey(K::LogicalKey),
+    /// A mouse button.
+    MouseButton(K::MouseButton),
+}
+
+impl<K: KeyTypes> PartialEq for Trigger<K> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Trigger::PhysicalKey(a), Trigger::PhysicalKey(b)) => a == b,
+            (Trigger::LogicalKey(a), Trigger::LogicalKey(b)) => a == b,
+            (Trigger::MouseButton(a), Trigger::MouseButton(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<K: KeyTypes> Eq for Trigger<K> {}
+
+/// Maps a [`Trigger`] and required [`InputState`]/[`KeyMods`] to a user-defined action.
+///
+/// `mods_mask` selects which modifier keys actually matter for this binding: only the bits set in
+/// the mask are compared against `mods`, so e.g. a binding can require Ctrl held while ignoring
+/// whether Shift is also down.
+#[derive(Clone, Debug)]
+pub struct Binding<K: KeyTypes> {
+    /// What triggers this binding.
+    pub trigger: Trigger<K>,
+    /// The modifiers required (after masking with `mods_mask`).
+    pub mods: KeyMods,
+    /// Which modifier keys are checked against `mods`.
+    pub mods_mask: KeyMods,
+    /// The [`InputState`] the trigger must be in for the binding to fire.
+    pub state: InputState,
+}
+
+impl<K: KeyTypes> PartialEq for Binding<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.trigger == other.trigger
+            && self.mods == other.mods
+            && self.mods_mask == other.mods_mask
+            && self.state == other.state
+    }
+}
+
+impl<K: KeyTypes> Eq for Binding<K> {}
+
+impl<K: KeyTypes> Binding<K> {
+    /// Create a new binding that doesn't care about modifier keys.
+    #[inline]
+    pub fn new(trigger: Trigger<K>, state: InputState) -> Self {
+        Self {
+            trigger,
+            mods: KeyMods::default(),
+            mods_mask: KeyMods::default(),
+            state,
+        }
+    }
+
+    /// Require `mods` (restricted to the keys set in `mods_mask`) to be held for this binding to fire.
+    #[inline]
+    pub fn with_mods(mut self, mods: KeyMods, mods_mask: KeyMods) -> Self {
+        self.mods = mods;
+        self.mods_mask = mods_mask;
+        self
+    }
+
+    fn is_triggered(&self, input: &Input<K>) -> bool {
+        let current_state = match self.trigger {
+            Trigger::PhysicalKey(key) => input.key_state(key),
+            Trigger::LogicalKey(key) => input.logical_key_state(key),
+            Trigger::MouseButton(button) => input.mouse_button_state(button),
+        };
+
+        current_state == Some(self.state) && input.key_mods().mask(self.mods_mask) == self.mods.mask(self.mods_mask)
+    }
+}
+
+/// Maps input [`Binding`]s to abstract, user-defined actions, so games can ask
+/// "did the Jump action fire" instead of checking raw keys.
+#[derive(Debug)]
+pub struct Bindings<K: KeyTypes, A> {
+    bindings: Vec<(Binding<K>, A)>,
+}
+
+impl<K: KeyTypes, A> Bindings<K, A> {
+    /// Create an empty set of bindings.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Register a binding for an action.
+    pub fn add(&mut self, binding: Binding<K>, action: A) -> &mut Self {
+        self.bindings.push((binding, action));
+        self
+    }
+
+    /// Remove a previously registered binding, returning its action if it was found.
+    pub fn remove(&mut self, binding: &Binding<K>) -> Option<A> {
+        let index = self.bindings.iter().position(|(b, _)| b == binding)?;
+        Some(self.bindings.remove(index).1)
+    }
+
+    /// Every action whose binding matched the current state of `input`.
+    pub fn triggered<'a>(&'a self, input: &'a Input<K>) -> impl Iterator<Item = &'a A> + 'a {
+        self.bindings
+            .iter()
+            .filter(move |(binding, _)| binding.is_triggered(input))
+            .map(|(_, action)| action)
+    }
+}
+
+impl<K: KeyTypes, A> Default for Bindings<K, A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
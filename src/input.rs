@@ -3,12 +3,32 @@ use rustc_hash::FxHashMap;
 
 #[cfg(feature = "winit")]
 use winit::{
-    event::{ElementState, Modifiers, MouseButton, WindowEvent},
+    event::{ElementState, Modifiers, MouseButton, MouseScrollDelta, WindowEvent},
     keyboard::{Key, KeyCode, ModifiersKeyState, NamedKey, PhysicalKey},
 };
 
+/// Identifies a connected gamepad.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadId(pub u32);
+
+/// The phase of a touch point's lifetime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TouchPhase {
+    /// A new touch point has appeared.
+    Started,
+    /// An existing touch point has moved.
+    Moved,
+    /// A touch point has been lifted.
+    Ended,
+    /// A touch point's tracking was cancelled by the platform.
+    Cancelled,
+}
+
 /// Keyboard modifiers.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyMods {
     /// Left "shift" key.
     pub lshift: bool,
@@ -30,6 +50,7 @@ pub struct KeyMods {
 
 /// Input state of a mouse button/keyboard key.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InputState {
     /// The button has just been pressed.
     Pressed,
@@ -41,6 +62,23 @@ pub enum InputState {
     Released,
 }
 
+impl KeyMods {
+    /// Keep only the modifier flags selected by `mask`, clearing the rest.
+    #[inline]
+    pub fn mask(self, mask: KeyMods) -> KeyMods {
+        KeyMods {
+            lshift: self.lshift && mask.lshift,
+            rshift: self.rshift && mask.rshift,
+            lalt: self.lalt && mask.lalt,
+            ralt: self.ralt && mask.ralt,
+            lcontrol: self.lcontrol && mask.lcontrol,
+            rcontrol: self.rcontrol && mask.rcontrol,
+            lsuper: self.lsuper && mask.lsuper,
+            rsuper: self.rsuper && mask.rsuper,
+        }
+    }
+}
+
 impl InputState {
     /// The state is [`InputState::Pressed`].
     #[inline]
@@ -61,6 +99,12 @@ impl InputState {
     }
 }
 
+/// The default deadzone applied to gamepad axis values, see [`Input::set_gamepad_deadzone`].
+pub const DEFAULT_GAMEPAD_DEADZONE: f32 = 0.1;
+
+/// The default number of scroll pixels per line, see [`Input::set_pixels_per_line`].
+pub const DEFAULT_PIXELS_PER_LINE: f32 = 100.0;
+
 /// Input handler.
 #[derive(Debug)]
 pub struct Input<K: KeyTypes> {
@@ -70,6 +114,11 @@ pub struct Input<K: KeyTypes> {
     mouse_buttons: FxHashMap<K::MouseButton, InputState>,
     mouse_pos: (f32, f32),
     mouse_scroll: (f32, f32),
+    gamepad_buttons: FxHashMap<(GamepadId, K::GamepadButton), InputState>,
+    gamepad_axes: FxHashMap<(GamepadId, K::GamepadAxis), f32>,
+    gamepad_deadzone: f32,
+    touches: FxHashMap<u64, (f32, f32)>,
+    pixels_per_line: f32,
 }
 
 impl<K> Input<K>
@@ -85,6 +134,11 @@ where
             mouse_buttons: FxHashMap::default(),
             mouse_pos: (0., 0.),
             mouse_scroll: (0., 0.),
+            gamepad_buttons: FxHashMap::default(),
+            gamepad_axes: FxHashMap::default(),
+            gamepad_deadzone: DEFAULT_GAMEPAD_DEADZONE,
+            touches: FxHashMap::default(),
+            pixels_per_line: DEFAULT_PIXELS_PER_LINE,
         }
     }
 
@@ -95,6 +149,9 @@ where
         self.mouse_buttons.clear();
         self.mouse_pos = (0., 0.);
         self.mouse_scroll = (0., 0.);
+        self.gamepad_buttons.clear();
+        self.gamepad_axes.clear();
+        self.touches.clear();
     }
 
     /// Mouse cursor position.
@@ -103,12 +160,23 @@ where
         self.mouse_pos
     }
 
-    /// Mouse scroll value in lines (x, y).
+    /// Mouse scroll value in lines (x, y), accumulated over the current frame.
     #[inline]
     pub fn mouse_scroll(&self) -> (f32, f32) {
         self.mouse_scroll
     }
 
+    /// Set how many scroll pixels make up one line, for platforms that report scroll deltas
+    /// in pixels rather than lines.
+    ///
+    /// Only `InputEvent::from_winit_window_event` reads this value, to convert pixel deltas into
+    /// lines; [`InputEvent::MouseScroll`] values passed to [`Input::process_event`] are taken as
+    /// lines and are not converted.
+    #[inline]
+    pub fn set_pixels_per_line(&mut self, pixels_per_line: f32) {
+        self.pixels_per_line = pixels_per_line;
+    }
+
     /// Get current keyboard modifiers.
     #[inline]
     pub fn key_mods(&self) -> KeyMods {
@@ -145,6 +213,12 @@ where
             .map_or(false, InputState::is_released)
     }
 
+    /// The current [`InputState`] of a physical key, if it has any state recorded.
+    #[inline]
+    pub fn key_state(&self, scancode: K::KeyCode) -> Option<InputState> {
+        self.keys.get(&scancode).copied()
+    }
+
     /// All input states of logical keys.
     #[inline]
     pub fn logical_keys(&self) -> &FxHashMap<K::LogicalKey, InputState> {
@@ -175,6 +249,12 @@ where
             .map_or(false, InputState::is_released)
     }
 
+    /// The current [`InputState`] of a logical key, if it has any state recorded.
+    #[inline]
+    pub fn logical_key_state(&self, key: K::LogicalKey) -> Option<InputState> {
+        self.logical_keys.get(&key).copied()
+    }
+
     /// All input states of mouse buttons.
     #[inline]
     pub fn mouse_buttons(&self) -> &FxHashMap<K::MouseButton, InputState> {
@@ -205,6 +285,73 @@ where
             .map_or(false, InputState::is_released)
     }
 
+    /// The current [`InputState`] of a mouse button, if it has any state recorded.
+    #[inline]
+    pub fn mouse_button_state(&self, button: K::MouseButton) -> Option<InputState> {
+        self.mouse_buttons.get(&button).copied()
+    }
+
+    /// Returns `true` if a gamepad button has just been pressed.
+    #[inline]
+    pub fn is_gamepad_button_pressed(&self, id: GamepadId, button: K::GamepadButton) -> bool {
+        self.gamepad_buttons
+            .get(&(id, button))
+            .map_or(false, InputState::is_pressed)
+    }
+
+    /// Returns `true` if a gamepad button is down.
+    #[inline]
+    pub fn is_gamepad_button_down(&self, id: GamepadId, button: K::GamepadButton) -> bool {
+        self.gamepad_buttons
+            .get(&(id, button))
+            .map_or(false, InputState::is_any_down)
+    }
+
+    /// Returns `true` if a gamepad button has just been released.
+    #[inline]
+    pub fn is_gamepad_button_released(&self, id: GamepadId, button: K::GamepadButton) -> bool {
+        self.gamepad_buttons
+            .get(&(id, button))
+            .map_or(false, InputState::is_released)
+    }
+
+    /// The current [`InputState`] of a gamepad button, if it has any state recorded.
+    #[inline]
+    pub fn gamepad_button_state(&self, id: GamepadId, button: K::GamepadButton) -> Option<InputState> {
+        self.gamepad_buttons.get(&(id, button)).copied()
+    }
+
+    /// The current value of a gamepad axis, with [`Input::set_gamepad_deadzone`] applied.
+    #[inline]
+    pub fn axis_value(&self, id: GamepadId, axis: K::GamepadAxis) -> f32 {
+        let value = self.gamepad_axes.get(&(id, axis)).copied().unwrap_or(0.);
+
+        if value.abs() < self.gamepad_deadzone {
+            0.
+        } else {
+            value
+        }
+    }
+
+    /// Set the deadzone applied to gamepad axis values by [`Input::axis_value`].
+    ///
+    /// Any axis value whose absolute value is below this threshold is reported as `0.0`.
+    #[inline]
+    pub fn set_gamepad_deadzone(&mut self, deadzone: f32) {
+        self.gamepad_deadzone = deadzone;
+    }
+
+    /// All currently-active touch points, keyed by touch id.
+    #[inline]
+    pub fn touches(&self) -> &FxHashMap<u64, (f32, f32)> {
+        &self.touches
+    }
+
+    /// The lowest-id active touch point, if any finger is currently touching.
+    pub fn primary_touch(&self) -> Option<(u64, (f32, f32))> {
+        self.touches.iter().min_by_key(|(id, _)| **id).map(|(id, pos)| (*id, *pos))
+    }
+
     pub fn update_keys(&mut self) {
         self.keys.retain(|_, state| match state {
             InputState::Pressed => {
@@ -232,6 +379,17 @@ where
             InputState::Down => true,
             InputState::Released => false,
         });
+
+        self.gamepad_buttons.retain(|_, state| match state {
+            InputState::Pressed => {
+                *state = InputState::Down;
+                true
+            }
+            InputState::Down => true,
+            InputState::Released => false,
+        });
+
+        self.mouse_scroll = (0.0, 0.0);
     }
 
     pub fn process_event(&mut self, event: InputEvent<K>) {
@@ -260,8 +418,28 @@ where
                 self.mouse_pos = (mouse_x, mouse_y);
             }
             InputEvent::MouseScroll(scroll_x, scroll_y) => {
-                self.mouse_scroll = (scroll_x, scroll_y);
+                self.mouse_scroll.0 += scroll_x;
+                self.mouse_scroll.1 += scroll_y;
+            }
+            InputEvent::GamepadButton { id, button, state } => {
+                self.gamepad_buttons.insert((id, button), state);
+            }
+            InputEvent::GamepadAxis { id, axis, value } => {
+                self.gamepad_axes.insert((id, axis), value);
             }
+            InputEvent::GamepadConnected { id: _ } => {}
+            InputEvent::GamepadDisconnected { id } => {
+                self.gamepad_buttons.retain(|(gid, _), _| *gid != id);
+                self.gamepad_axes.retain(|(gid, _), _| *gid != id);
+            }
+            InputEvent::Touch { id, phase, position } => match phase {
+                TouchPhase::Started | TouchPhase::Moved => {
+                    self.touches.insert(id, position);
+                }
+                TouchPhase::Ended | TouchPhase::Cancelled => {
+                    self.touches.remove(&id);
+                }
+            },
         }
     }
 }
@@ -270,9 +448,21 @@ pub trait KeyTypes: Sized {
     type KeyCode: Copy + Debug + Eq + Hash;
     type LogicalKey: Copy + Debug + Eq + Hash;
     type MouseButton: Copy + Debug + Eq + Hash;
+    /// A gamepad button, as reported by the input source (e.g. `gilrs::Button` under the `gilrs` feature).
+    type GamepadButton: Copy + Debug + Eq + Hash;
+    /// A gamepad analog axis, as reported by the input source (e.g. `gilrs::Axis` under the `gilrs` feature).
+    type GamepadAxis: Copy + Debug + Eq + Hash;
 }
 
-#[derive(Clone, Debug)]
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "K::KeyCode: serde::Serialize, K::LogicalKey: serde::Serialize, K::MouseButton: serde::Serialize, K::GamepadButton: serde::Serialize, K::GamepadAxis: serde::Serialize",
+        deserialize = "K::KeyCode: serde::Deserialize<'de>, K::LogicalKey: serde::Deserialize<'de>, K::MouseButton: serde::Deserialize<'de>, K::GamepadButton: serde::Deserialize<'de>, K::GamepadAxis: serde::Deserialize<'de>"
+    ))
+)]
 pub enum InputEvent<K: KeyTypes> {
     Key {
         key: K::KeyCode,
@@ -287,11 +477,85 @@ pub enum InputEvent<K: KeyTypes> {
         state: InputState,
     },
     MouseScroll(f32, f32),
+    GamepadButton {
+        id: GamepadId,
+        button: K::GamepadButton,
+        state: InputState,
+    },
+    GamepadAxis {
+        id: GamepadId,
+        axis: K::GamepadAxis,
+        value: f32,
+    },
+    GamepadConnected {
+        id: GamepadId,
+    },
+    GamepadDisconnected {
+        id: GamepadId,
+    },
+    Touch {
+        id: u64,
+        phase: TouchPhase,
+        position: (f32, f32),
+    },
+}
+
+// Hand-written rather than `#[derive(Clone)]`: deriving would require `K: Clone`, but `K` is only
+// ever used as a type-level marker, never stored — what actually needs to be `Clone` is each associated type, and
+// `KeyTypes` already guarantees those are `Copy`.
+impl<K: KeyTypes> Clone for InputEvent<K> {
+    fn clone(&self) -> Self {
+        match self {
+            InputEvent::Key {
+                key,
+                logical_key,
+                repeat,
+                state,
+            } => InputEvent::Key {
+                key: *key,
+                logical_key: *logical_key,
+                repeat: *repeat,
+                state: *state,
+            },
+            InputEvent::Modifiers(mods) => InputEvent::Modifiers(*mods),
+            InputEvent::MouseMoved(x, y) => InputEvent::MouseMoved(*x, *y),
+            InputEvent::MouseButton { button, state } => InputEvent::MouseButton {
+                button: *button,
+                state: *state,
+            },
+            InputEvent::MouseScroll(x, y) => InputEvent::MouseScroll(*x, *y),
+            InputEvent::GamepadButton { id, button, state } => InputEvent::GamepadButton {
+                id: *id,
+                button: *button,
+                state: *state,
+            },
+            InputEvent::GamepadAxis { id, axis, value } => InputEvent::GamepadAxis {
+                id: *id,
+                axis: *axis,
+                value: *value,
+            },
+            InputEvent::GamepadConnected { id } => InputEvent::GamepadConnected { id: *id },
+            InputEvent::GamepadDisconnected { id } => InputEvent::GamepadDisconnected { id: *id },
+            InputEvent::Touch {
+                id,
+                phase,
+                position,
+            } => InputEvent::Touch {
+                id: *id,
+                phase: *phase,
+                position: *position,
+            },
+        }
+    }
 }
 
 #[cfg(feature = "winit")]
 impl InputEvent<WindowEvent> {
-    pub fn from_winit_window_event(event: &WindowEvent) -> Option<Self> {
+    /// Convert a winit [`WindowEvent`] into an [`InputEvent`].
+    ///
+    /// `input`'s configured [`Input::set_pixels_per_line`] is used to convert
+    /// [`MouseScrollDelta::PixelDelta`] into lines.
+    pub fn from_winit_window_event(event: &WindowEvent, input: &Input<WindowEvent>) -> Option<Self> {
         match event {
             WindowEvent::KeyboardInput {
                 device_id: _,
@@ -329,14 +593,36 @@ impl InputEvent<WindowEvent> {
                 device_id: _,
                 delta,
                 phase: _,
-            } => {
-                todo!()
-            }
+            } => match *delta {
+                MouseScrollDelta::LineDelta(x, y) => Some(InputEvent::MouseScroll(x, y)),
+                MouseScrollDelta::PixelDelta(pos) => Some(InputEvent::MouseScroll(
+                    pos.x as f32 / input.pixels_per_line,
+                    pos.y as f32 / input.pixels_per_line,
+                )),
+            },
+            WindowEvent::Touch(touch) => Some(InputEvent::Touch {
+                id: touch.id,
+                phase: touch.phase.into(),
+                position: (touch.location.x as _, touch.location.y as _),
+            }),
             _ => None,
         }
     }
 }
 
+#[cfg(feature = "winit")]
+impl From<winit::event::TouchPhase> for TouchPhase {
+    #[inline]
+    fn from(phase: winit::event::TouchPhase) -> Self {
+        match phase {
+            winit::event::TouchPhase::Started => TouchPhase::Started,
+            winit::event::TouchPhase::Moved => TouchPhase::Moved,
+            winit::event::TouchPhase::Ended => TouchPhase::Ended,
+            winit::event::TouchPhase::Cancelled => TouchPhase::Cancelled,
+        }
+    }
+}
+
 #[cfg(feature = "winit")]
 impl From<ElementState> for InputState {
     #[inline]
@@ -369,4 +655,99 @@ impl KeyTypes for WindowEvent {
     type KeyCode = KeyCode;
     type LogicalKey = NamedKey;
     type MouseButton = MouseButton;
+    #[cfg(feature = "gilrs")]
+    type GamepadButton = gilrs::Button;
+    #[cfg(not(feature = "gilrs"))]
+    type GamepadButton = ();
+    #[cfg(feature = "gilrs")]
+    type GamepadAxis = gilrs::Axis;
+    #[cfg(not(feature = "gilrs"))]
+    type GamepadAxis = ();
+}
+
+#[cfg(feature = "gilrs")]
+impl From<gilrs::GamepadId> for GamepadId {
+    #[inline]
+    fn from(id: gilrs::GamepadId) -> Self {
+        GamepadId(usize::from(id) as u32)
+    }
+}
+
+/// Converts `gilrs` events into [`InputEvent`]s, for any `K` whose gamepad types match `gilrs`'s.
+#[cfg(feature = "gilrs")]
+impl<K> InputEvent<K>
+where
+    K: KeyTypes<GamepadButton = gilrs::Button, GamepadAxis = gilrs::Axis>,
+{
+    /// Convert a `gilrs` event into an [`InputEvent`].
+    pub fn from_gilrs_event(event: &gilrs::Event) -> Option<Self> {
+        let id = GamepadId::from(event.id);
+
+        match event.event {
+            gilrs::EventType::ButtonPressed(button, _) => Some(InputEvent::GamepadButton {
+                id,
+                button,
+                state: InputState::Pressed,
+            }),
+            gilrs::EventType::ButtonReleased(button, _) => Some(InputEvent::GamepadButton {
+                id,
+                button,
+                state: InputState::Released,
+            }),
+            gilrs::EventType::AxisChanged(axis, value, _) => {
+                Some(InputEvent::GamepadAxis { id, axis, value })
+            }
+            gilrs::EventType::Connected => Some(InputEvent::GamepadConnected { id }),
+            gilrs::EventType::Disconnected => Some(InputEvent::GamepadDisconnected { id }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct TestKeys;
+
+    impl KeyTypes for TestKeys {
+        type KeyCode = u8;
+        type LogicalKey = u8;
+        type MouseButton = u8;
+        type GamepadButton = u8;
+        type GamepadAxis = u8;
+    }
+
+    #[test]
+    fn mouse_scroll_accumulates_within_a_frame_and_resets_on_update_keys() {
+        let mut input = Input::<TestKeys>::new();
+
+        input.process_event(InputEvent::MouseScroll(1.0, 2.0));
+        input.process_event(InputEvent::MouseScroll(0.5, -0.5));
+        assert_eq!(input.mouse_scroll(), (1.5, 1.5));
+
+        input.update_keys();
+        assert_eq!(input.mouse_scroll(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn gamepad_axis_deadzone_masks_small_values_only() {
+        let mut input = Input::<TestKeys>::new();
+        let id = GamepadId(0);
+
+        input.process_event(InputEvent::GamepadAxis {
+            id,
+            axis: 0,
+            value: 0.05,
+        });
+        assert_eq!(input.axis_value(id, 0), 0.0);
+
+        input.process_event(InputEvent::GamepadAxis {
+            id,
+            axis: 0,
+            value: -0.5,
+        });
+        assert_eq!(input.axis_value(id, 0), -0.5);
+    }
 }
@@ -2,14 +2,28 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
 use core::time::Duration;
 
+mod bindings;
+mod input;
+mod recording;
+
+pub use bindings::{Binding, Bindings, Trigger};
+pub use input::{
+    GamepadId, Input, InputEvent, InputState, KeyMods, KeyTypes, TouchPhase,
+    DEFAULT_GAMEPAD_DEADZONE, DEFAULT_PIXELS_PER_LINE,
+};
+pub use recording::{Recording, Replayer};
+
 /// Implemented based on <https://gafferongames.com/post/fix_your_timestep>.
 #[derive(Clone, Debug)]
 pub struct GameLoop {
     target_frame_time: Duration,
     max_frame_time: Duration,
     accumulated_time: Duration,
+    max_updates_per_call: Option<u32>,
 
     total_num_updates: u64,
     total_time_passed: Duration,
@@ -23,6 +37,7 @@ impl GameLoop {
             target_frame_time,
             max_frame_time,
             accumulated_time: Duration::ZERO,
+            max_updates_per_call: None,
 
             total_num_updates: 0,
             total_time_passed: Duration::ZERO,
@@ -42,6 +57,20 @@ impl GameLoop {
         self.max_frame_time = time;
     }
 
+    /// Set a cap on how many updates a single [`GameLoop::update`] call can run.
+    ///
+    /// This guards against the "spiral of death": without a cap, a target frame time that's too
+    /// small (or a caller feeding in huge `elapsed` values) can make the update loop fall further
+    /// and further behind trying to catch up. Once the cap is hit, the leftover accumulated time
+    /// is discarded (reported as [`UpdateResult::dropped_time`]) rather than carried forward, so
+    /// the game can detect it's running too slow and degrade gracefully instead of freezing.
+    ///
+    /// `None` (the default) means no cap.
+    #[inline]
+    pub fn set_max_updates_per_call(&mut self, max_updates: Option<u32>) {
+        self.max_updates_per_call = max_updates;
+    }
+
     /// Perform all calculations for an update.
     /// 
     /// You can do something like:
@@ -69,13 +98,30 @@ impl GameLoop {
             elapsed
         };
 
-        let mut num_updates = 0;
+        let mut num_updates: u64 = 0;
+        let mut capped = false;
 
         while self.accumulated_time > self.target_frame_time {
+            if self
+                .max_updates_per_call
+                .is_some_and(|max| num_updates >= max as u64)
+            {
+                capped = true;
+                break;
+            }
+
             self.accumulated_time -= self.target_frame_time;
             num_updates += 1;
         }
 
+        let dropped_time = if capped {
+            core::mem::replace(&mut self.accumulated_time, Duration::ZERO)
+        } else {
+            Duration::ZERO
+        };
+
+        let first_update_tick = self.total_num_updates;
+
         self.total_num_updates += num_updates;
 
         let blending_factor =
@@ -84,11 +130,13 @@ impl GameLoop {
         UpdateResult {
             num_updates,
             total_num_updates: self.total_num_updates,
+            update_tick: first_update_tick,
 
             frame_time: self.target_frame_time,
             blending_factor,
 
             total_time_passed: self.total_time_passed,
+            dropped_time,
 
             exit: false,
         }
@@ -103,6 +151,13 @@ pub struct UpdateResult {
     /// Total number of updates since [`GameLoop`]'s creation.
     pub total_num_updates: u64,
 
+    /// The update-tick count of the update currently being run by [`UpdateResult::run()`] or
+    /// [`UpdateResult::run_result()`]. Unlike [`UpdateResult::total_num_updates`] (fixed for the
+    /// whole `update()` call), this advances by one before each closure invocation, so it uniquely
+    /// identifies every update in a catch-up batch — this is the tick recording/replay should key
+    /// off (see [`Recording`](crate::Recording)).
+    pub update_tick: u64,
+
     /// Time between previous and current update.
     pub frame_time: Duration,
     /// Blending between current and next frames. Primarily useful for rendering.
@@ -112,6 +167,10 @@ pub struct UpdateResult {
     /// This is a sum of the provided `elapsed` arguments.
     pub total_time_passed: Duration,
 
+    /// Simulation time discarded because [`GameLoop::set_max_updates_per_call`]'s cap was hit.
+    /// Zero unless a cap is set and was exceeded this call.
+    pub dropped_time: Duration,
+
     /// Whether to exit next iteration.
     /// This is only useful in [`UpdateResult::run()`] or [`UpdateResult::run_result()`].
     pub exit: bool,
@@ -128,6 +187,8 @@ impl UpdateResult {
         F: FnMut(&mut Self),
     {
         for _i in 0..self.num_updates {
+            self.update_tick += 1;
+
             (func)(&mut self);
 
             if self.exit {
@@ -148,6 +209,8 @@ impl UpdateResult {
         F: FnMut(&mut Self) -> Result<(), E>,
     {
         for _i in 0..self.num_updates {
+            self.update_tick += 1;
+
             (func)(&mut self)?;
 
             if self.exit {
@@ -158,3 +221,23 @@ impl UpdateResult {
         Ok(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_updates_cap_drops_leftover_time_instead_of_carrying_it_forward() {
+        let mut game_loop = GameLoop::new(Duration::from_millis(10), Duration::from_secs(1));
+        game_loop.set_max_updates_per_call(Some(2));
+
+        let result = game_loop.update(Duration::from_millis(55));
+        assert_eq!(result.num_updates, 2);
+        assert_eq!(result.dropped_time, Duration::from_millis(35));
+
+        // the dropped time must be discarded, not carried into the next call
+        let result = game_loop.update(Duration::ZERO);
+        assert_eq!(result.num_updates, 0);
+        assert_eq!(result.dropped_time, Duration::ZERO);
+    }
+}
@@ -0,0 +1,145 @@
+use alloc::vec::Vec;
+
+use crate::input::{Input, InputEvent, KeyTypes};
+
+/// A recorded sequence of [`InputEvent`]s, each tagged with the
+/// [`UpdateResult::update_tick`](crate::UpdateResult::update_tick) at which it was processed.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "InputEvent<K>: serde::Serialize",
+        deserialize = "InputEvent<K>: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Recording<K: KeyTypes>(Vec<(u64, InputEvent<K>)>);
+
+impl<K: KeyTypes> Recording<K> {
+    /// Create an empty recording.
+    #[inline]
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Append an event tagged with the tick it was processed at.
+    #[inline]
+    pub fn push(&mut self, tick: u64, event: InputEvent<K>) {
+        self.0.push((tick, event));
+    }
+
+    /// All recorded `(tick, event)` pairs, in the order they were processed.
+    #[inline]
+    pub fn events(&self) -> &[(u64, InputEvent<K>)] {
+        &self.0
+    }
+}
+
+impl<K: KeyTypes> Default for Recording<K> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: KeyTypes> Input<K> {
+    /// Process an event and additionally append it to `recording`, tagged with `tick`, which
+    /// should be the current [`UpdateResult::update_tick`](crate::UpdateResult::update_tick).
+    pub fn process_event_recorded(&mut self, recording: &mut Recording<K>, tick: u64, event: InputEvent<K>) {
+        recording.push(tick, event.clone());
+        self.process_event(event);
+    }
+}
+
+/// Replays a [`Recording`] into an [`Input`], driven by
+/// [`UpdateResult::update_tick`](crate::UpdateResult::update_tick).
+#[derive(Clone, Debug)]
+pub struct Replayer<K: KeyTypes> {
+    recording: Recording<K>,
+    next_index: usize,
+}
+
+impl<K: KeyTypes> Replayer<K> {
+    /// Create a replayer for the given recording, starting from its first event.
+    #[inline]
+    pub fn new(recording: Recording<K>) -> Self {
+        Self {
+            recording,
+            next_index: 0,
+        }
+    }
+
+    /// Feed every event recorded for `tick` into `input`. Call once per update, passing the
+    /// current [`UpdateResult::update_tick`](crate::UpdateResult::update_tick).
+    ///
+    /// Events are replayed in recorded order: replay stops at the first unreplayed event whose
+    /// tick is not `tick`, so an event recorded for a tick that was never passed here keeps every
+    /// later event from being replayed.
+    pub fn replay_tick(&mut self, tick: u64, input: &mut Input<K>) {
+        while let Some((event_tick, _)) = self.recording.0.get(self.next_index) {
+            if *event_tick != tick {
+                break;
+            }
+
+            let (_, event) = self.recording.0[self.next_index].clone();
+            input.process_event(event);
+            self.next_index += 1;
+        }
+    }
+
+    /// Returns `true` if every recorded event has been replayed.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.next_index >= self.recording.0.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::InputState;
+
+    #[derive(Debug)]
+    struct TestKeys;
+
+    impl KeyTypes for TestKeys {
+        type KeyCode = u8;
+        type LogicalKey = u8;
+        type MouseButton = u8;
+        type GamepadButton = u8;
+        type GamepadAxis = u8;
+    }
+
+    #[test]
+    fn record_and_replay_round_trip() {
+        let mut recording = Recording::<TestKeys>::new();
+        let mut recorder_input = Input::<TestKeys>::new();
+
+        recorder_input.process_event_recorded(
+            &mut recording,
+            1,
+            InputEvent::Key {
+                key: 1,
+                logical_key: None,
+                repeat: false,
+                state: InputState::Pressed,
+            },
+        );
+        recorder_input.process_event_recorded(&mut recording, 3, InputEvent::MouseMoved(1.0, 2.0));
+
+        let mut replay_input = Input::<TestKeys>::new();
+        let mut replayer = Replayer::new(recording);
+
+        replayer.replay_tick(1, &mut replay_input);
+        assert!(replay_input.is_key_pressed(1));
+        assert!(!replayer.is_finished());
+
+        // no events recorded at tick 2, so the replayed state shouldn't change
+        replayer.replay_tick(2, &mut replay_input);
+        assert!(!replayer.is_finished());
+
+        replayer.replay_tick(3, &mut replay_input);
+        assert_eq!(replay_input.mouse_pos(), (1.0, 2.0));
+        assert!(replayer.is_finished());
+    }
+}